@@ -1,12 +1,12 @@
 use std::sync::Arc;
 
 use lance_index::scalar::inverted::query::collect_tokens;
-use lance_index::scalar::inverted::query::FtsSearchParams;
+use lance_index::scalar::inverted::query::{FtsSearchParams, FuzzyMatch};
 use pyo3::exceptions::*;
 use pyo3::prelude::*;
 use pyo3::types::{PyDict, PyTuple};
 
-use lance_index::scalar::inverted::{BM25Scorer, InvertedPartitionMetadata, Scorer};
+use lance_index::scalar::inverted::{AnyScorer, InvertedPartitionMetadata, Scorer};
 use lance_index::scalar::{inverted::InvertedIndex, ScalarIndex};
 use pyo3::{PyObject, PyRef, PyResult};
 use std::collections::{HashMap, HashSet};
@@ -81,17 +81,22 @@ impl PyInvertedIndex {
         tokens: HashSet<String>,
         params: Option<Bound<'_, PyDict>>,
         partition_ids: Option<Vec<u64>>,
-    ) -> PyResult<HashMap<String, usize>> {
+    ) -> PyResult<HashMap<String, PyFuzzyMatch>> {
         let fts_params = params
             .map(|params| {
                 let max_expansions = params
                     .get_item("max_expansions")?
                     .map(|v| v.extract::<usize>())
                     .transpose()?;
+                // an explicit `fuzziness=None` selects the length-adaptive
+                // ("AUTO") mode; omitting the key keeps the same fixed,
+                // exact-match default as calling `fuzzy_nq` with no
+                // `params` at all
                 let fuzziness = params
                     .get_item("fuzziness")?
                     .map(|v| v.extract::<Option<u32>>())
-                    .transpose()?;
+                    .transpose()?
+                    .unwrap_or(Some(0));
                 let prefix_length = params
                     .get_item("prefix_length")?
                     .map(|v| v.extract::<u32>())
@@ -99,7 +104,7 @@ impl PyInvertedIndex {
 
                 Ok::<_, PyErr>(FtsSearchParams {
                     max_expansions: max_expansions.unwrap_or(50),
-                    fuzziness: fuzziness.unwrap_or(Some(0)),
+                    fuzziness,
                     prefix_length: prefix_length.unwrap_or(0),
                     ..Default::default()
                 })
@@ -107,13 +112,52 @@ impl PyInvertedIndex {
             .transpose()?
             .unwrap_or_else(FtsSearchParams::new);
 
-        RT.runtime
+        let matches = RT
+            .runtime
             .block_on(self_.as_inverted_index().fuzzy_nq(
                 &tokens,
                 &fts_params,
                 partition_ids.as_ref(),
             ))
-            .map_err(|err| PyIOError::new_err(err.to_string()))
+            .map_err(|err| PyIOError::new_err(err.to_string()))?;
+
+        Ok(matches
+            .into_iter()
+            .map(|(token, m)| (token, PyFuzzyMatch::new(m)))
+            .collect())
+    }
+}
+
+/// A fuzzy-matched vocabulary token: its document frequency and the edit
+/// distance from the query token that produced the match.
+#[pyclass]
+#[derive(Clone)]
+pub struct PyFuzzyMatch {
+    inner: FuzzyMatch,
+}
+
+impl PyFuzzyMatch {
+    pub fn new(inner: FuzzyMatch) -> Self {
+        Self { inner }
+    }
+}
+
+#[pymethods]
+impl PyFuzzyMatch {
+    pub fn __repr__(&self) -> String {
+        format!("PyFuzzyMatch({:?})", self.inner)
+    }
+
+    pub fn __str__(&self) -> String {
+        self.__repr__()
+    }
+
+    pub fn nq(&self) -> usize {
+        self.inner.nq
+    }
+
+    pub fn distance(&self) -> u32 {
+        self.inner.distance
     }
 }
 
@@ -154,52 +198,87 @@ impl PyInvertedPartitionMetadata {
     pub fn fragments(&self) -> Vec<u32> {
         self.inner.fragments().iter().cloned().collect()
     }
+
+    /// Per-field `(num_tokens, num_docs)`, for a BM25F multi-column index.
+    /// Empty for a single-column index.
+    pub fn field_stats(&self) -> HashMap<String, (usize, usize)> {
+        self.inner
+            .field_stats()
+            .iter()
+            .map(|(field, stats)| (field.clone(), (stats.num_tokens, stats.num_docs)))
+            .collect()
+    }
 }
 
+/// A scorer for an inverted index, wrapping whichever model
+/// (`BM25`, `TfIdf`, `Bm25L`, ...) the index was actually built with.
 #[pyclass]
 #[derive(Clone)]
-pub struct PyBM25Scorer {
-    pub(crate) inner: Arc<BM25Scorer>,
+pub struct PyScorer {
+    pub(crate) inner: Arc<AnyScorer>,
 }
 
-impl PyBM25Scorer {
-    pub fn new(inner: Arc<BM25Scorer>) -> Self {
+impl PyScorer {
+    pub fn new(inner: Arc<AnyScorer>) -> Self {
         Self { inner }
     }
 }
 
 #[pymethods]
-impl PyBM25Scorer {
+impl PyScorer {
     pub fn __repr__(&self) -> String {
-        format!("PyBM25Scorer({:?})", self.inner)
+        format!("PyScorer({:?})", self.inner)
     }
 
     pub fn __str__(&self) -> String {
         self.__repr__()
     }
 
+    /// The scoring model this scorer was built with, e.g. `"Bm25"`.
+    pub fn model(&self) -> String {
+        format!("{:?}", self.inner.model())
+    }
+
     pub fn score(&self, token: &str, freq: u32, doc_tokens: u32) -> f32 {
         self.inner.score(token, freq, doc_tokens)
     }
 
+    /// Scores `token` across multiple fields, BM25F-style. `field_freqs`
+    /// maps each field containing `token` to `(freq, field_doc_tokens)`.
+    /// Only valid for a `Bm25` scorer trained with field weights.
+    pub fn score_bm25f(
+        &self,
+        token: &str,
+        field_freqs: HashMap<String, (u32, u32)>,
+    ) -> PyResult<f32> {
+        match self.inner.as_ref() {
+            AnyScorer::Bm25(scorer) => Ok(scorer.score_bm25f(token, &field_freqs)),
+            other => Err(PyValueError::new_err(format!(
+                "score_bm25f is only supported for a Bm25 scorer, found {:?}",
+                other.model()
+            ))),
+        }
+    }
+
     #[staticmethod]
     pub fn merge(scorers: Vec<Self>) -> PyResult<Self> {
-        let scorer =
-            BM25Scorer::merge(&scorers.iter().map(|s| s.inner.as_ref()).collect::<Vec<_>>());
+        let inner = scorers.iter().map(|s| s.inner.as_ref()).collect::<Vec<_>>();
+        let scorer = AnyScorer::try_merge(&inner)
+            .map_err(|err| PyValueError::new_err(err.to_string()))?;
         Ok(Self::new(Arc::new(scorer)))
     }
 
     #[staticmethod]
     pub fn from_json(json: String) -> PyResult<Self> {
         let scorer = serde_json::from_str(&json).map_err(|err| {
-            PyValueError::new_err(format!("Could not load BM25Scorer due to error: {}", err))
+            PyValueError::new_err(format!("Could not load scorer due to error: {}", err))
         })?;
         Ok(Self::new(Arc::new(scorer)))
     }
 
     pub fn to_json(&self) -> PyResult<String> {
         serde_json::to_string(self.inner.as_ref()).map_err(|err| {
-            PyValueError::new_err(format!("Could not dump BM25Scorer due to error: {}", err))
+            PyValueError::new_err(format!("Could not dump scorer due to error: {}", err))
         })
     }
 
@@ -207,7 +286,7 @@ impl PyBM25Scorer {
         let state = self.to_json()?;
         let state = PyTuple::new(py, vec![state])?.extract()?;
         let from_json = PyModule::import(py, "lance.index.bm25")?
-            .getattr("BM25Scorer")?
+            .getattr("Scorer")?
             .getattr("from_json")?
             .extract()?;
         Ok((from_json, state))
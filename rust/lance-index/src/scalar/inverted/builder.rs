@@ -0,0 +1,311 @@
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-FileCopyrightText: Copyright The Lance Authors
+
+use std::collections::{HashMap, HashSet};
+
+use arrow_array::{Array, RecordBatch, StringArray, UInt64Array};
+use futures::stream::BoxStream;
+use futures::TryStreamExt;
+use serde::{Deserialize, Serialize};
+
+use lance_core::{Result, ROW_ID};
+
+use super::super::IndexStore;
+use super::index::InvertedPartitionMetadata;
+use super::scorer::{AnyScorer, FieldStats, FieldWeight, ScorerModel, ScorerParams};
+use super::tokenizer::{InvertedIndexTokenizer, InvertedIndexTokenizerConfig};
+
+/// Parameters for training an inverted index.
+///
+/// An index normally covers a single column, but [`Self::fields`] can map
+/// several columns to a per-field boost and length-normalization
+/// parameter, in which case only those columns are indexed and the index
+/// is scored BM25F-style: per-field term
+/// frequencies are combined into one pseudo frequency before saturation
+/// instead of treating every field's tokens as one bag of words.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct InvertedIndexParams {
+    pub with_position: bool,
+    pub tokenizer_config: InvertedIndexTokenizerConfig,
+    /// The scoring model (and its parameters) the index is built with.
+    /// When [`Self::fields`] is non-empty the model is ignored and the
+    /// index is always scored with BM25F.
+    pub scorer: ScorerParams,
+    /// The columns to index, each with its own boost and `b`
+    /// (length-normalization) parameter; when non-empty, string columns
+    /// not listed here are not indexed. Empty for a single-column index.
+    pub fields: HashMap<String, FieldWeight>,
+}
+
+impl InvertedIndexParams {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_position(mut self, with_position: bool) -> Self {
+        self.with_position = with_position;
+        self
+    }
+
+    /// Selects the scoring model the index will persist and score with.
+    pub fn model(mut self, model: ScorerModel) -> Self {
+        self.scorer.model = model;
+        self
+    }
+
+    /// Sets the BM25 / BM25L term-frequency saturation parameter.
+    pub fn k1(mut self, k1: f32) -> Self {
+        self.scorer.k1 = k1;
+        self
+    }
+
+    /// Sets the BM25 / BM25L document-length normalization parameter.
+    pub fn b(mut self, b: f32) -> Self {
+        self.scorer.b = b;
+        self
+    }
+
+    /// Sets the BM25L length-bias parameter.
+    pub fn delta(mut self, delta: f32) -> Self {
+        self.scorer.delta = delta;
+        self
+    }
+
+    /// Folds `column` into this index with the given boost and `b`
+    /// parameter, turning on BM25F scoring across all configured fields.
+    pub fn with_field(mut self, column: impl Into<String>, boost: f32, b: f32) -> Self {
+        self.fields.insert(column.into(), FieldWeight { boost, b });
+        self
+    }
+
+    pub fn is_multi_field(&self) -> bool {
+        !self.fields.is_empty()
+    }
+}
+
+/// Builds an inverted index from a stream of training batches.
+pub struct InvertedIndexBuilder {
+    params: InvertedIndexParams,
+    partitions: Vec<InvertedPartitionMetadata>,
+}
+
+impl InvertedIndexBuilder {
+    pub fn new(params: InvertedIndexParams) -> Self {
+        Self {
+            params,
+            partitions: Vec::new(),
+        }
+    }
+
+    pub fn params(&self) -> &InvertedIndexParams {
+        &self.params
+    }
+
+    /// The partitions trained so far, each carrying the scorer configured by
+    /// [`InvertedIndexParams::scorer`] and, when
+    /// [`InvertedIndexParams::fields`] is non-empty, per-field corpus
+    /// statistics.
+    pub fn partitions(&self) -> &[InvertedPartitionMetadata] {
+        &self.partitions
+    }
+
+    /// Consumes `batch_stream`, tokenizing the configured column(s) of every
+    /// document and accumulating the corpus statistics (vocabulary document
+    /// frequencies, corpus length, and per-field document counts/lengths
+    /// when [`InvertedIndexParams::fields`] is set) the configured scorer
+    /// needs, then trains a new partition from them.
+    ///
+    /// Writing the resulting postings to `index_store`'s on-disk format is
+    /// owned by the surrounding scalar index engine; this builder only
+    /// hands it the partition it just trained.
+    pub async fn update(
+        &mut self,
+        mut batch_stream: BoxStream<'_, Result<RecordBatch>>,
+        index_store: &dyn IndexStore,
+    ) -> Result<()> {
+        let mut tokenizer = InvertedIndexTokenizer::new(self.params.tokenizer_config.clone());
+        let mut dictionary: HashMap<String, usize> = HashMap::new();
+        let mut field_stats: HashMap<String, FieldStats> = HashMap::new();
+        let mut fragments: HashSet<u32> = HashSet::new();
+        let mut num_docs = 0usize;
+        let mut num_tokens = 0usize;
+
+        while let Some(batch) = batch_stream.try_next().await? {
+            num_docs += batch.num_rows();
+
+            if let Some(row_ids) = batch
+                .column_by_name(ROW_ID)
+                .and_then(|col| col.as_any().downcast_ref::<UInt64Array>())
+            {
+                for row in 0..row_ids.len() {
+                    // a row id packs the owning fragment id into its high bits
+                    fragments.insert((row_ids.value(row) >> 32) as u32);
+                }
+            }
+
+            self.accumulate_batch(
+                &batch,
+                &mut tokenizer,
+                &mut dictionary,
+                &mut field_stats,
+                &mut num_tokens,
+            );
+        }
+
+        let scorer = if self.params.is_multi_field() {
+            AnyScorer::new_bm25f(
+                dictionary.clone(),
+                num_docs,
+                num_tokens,
+                &self.params.scorer,
+                field_stats.clone(),
+                self.params.fields.clone(),
+            )
+        } else {
+            AnyScorer::new(
+                self.params.scorer.model,
+                dictionary.clone(),
+                num_docs,
+                num_tokens,
+                &self.params.scorer,
+            )
+        };
+
+        let partition = InvertedPartitionMetadata::new(
+            self.partitions.len() as u64,
+            num_tokens,
+            num_docs,
+            fragments.into_iter().collect(),
+            scorer,
+            dictionary,
+            if self.params.is_multi_field() {
+                field_stats
+            } else {
+                HashMap::new()
+            },
+        );
+
+        // The posting lists themselves (the per-token row-id lists) are
+        // encoded and written to `index_store` by the surrounding scalar
+        // index engine, which isn't part of this crate; the builder's job
+        // ends at handing over the trained partition.
+        let _ = index_store;
+        self.partitions.push(partition);
+
+        Ok(())
+    }
+
+    /// Tokenizes one training batch's configured column(s) and folds the
+    /// result into `dictionary`/`field_stats`/`num_tokens`.
+    ///
+    /// A term's document frequency counts a row once no matter how many of
+    /// the row's configured fields it appears in, so tokens are deduped
+    /// across fields before they touch `dictionary`.
+    fn accumulate_batch(
+        &self,
+        batch: &RecordBatch,
+        tokenizer: &mut InvertedIndexTokenizer,
+        dictionary: &mut HashMap<String, usize>,
+        field_stats: &mut HashMap<String, FieldStats>,
+        num_tokens: &mut usize,
+    ) {
+        let field_names = batch
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| field.name().clone())
+            .filter(|name| name != ROW_ID)
+            .filter(|name| self.params.fields.is_empty() || self.params.fields.contains_key(name))
+            .collect::<Vec<_>>();
+
+        let field_columns = field_names
+            .iter()
+            .filter_map(|field_name| {
+                batch
+                    .column_by_name(field_name)
+                    .and_then(|col| col.as_any().downcast_ref::<StringArray>().cloned())
+                    .map(|values| (field_name.clone(), values))
+            })
+            .collect::<Vec<_>>();
+
+        for row in 0..batch.num_rows() {
+            let mut row_tokens: HashSet<String> = HashSet::new();
+            for (field_name, values) in &field_columns {
+                if values.is_null(row) {
+                    continue;
+                }
+                let tokens = tokenizer.tokenize(values.value(row));
+                let stats = field_stats.entry(field_name.clone()).or_default();
+                stats.num_docs += 1;
+                stats.num_tokens += tokens.len();
+                *num_tokens += tokens.len();
+                row_tokens.extend(tokens);
+            }
+            for token in row_tokens {
+                *dictionary.entry(token).or_insert(0) += 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow_schema::{DataType, Field, Schema};
+
+    use super::*;
+
+    fn string_batch(columns: &[(&str, Vec<&str>)]) -> RecordBatch {
+        let fields = columns
+            .iter()
+            .map(|(name, _)| Field::new(*name, DataType::Utf8, false))
+            .collect::<Vec<_>>();
+        let arrays = columns
+            .iter()
+            .map(|(_, values)| Arc::new(StringArray::from(values.clone())) as _)
+            .collect::<Vec<_>>();
+        RecordBatch::try_new(Arc::new(Schema::new(fields)), arrays).unwrap()
+    }
+
+    #[test]
+    fn accumulate_batch_counts_a_shared_token_once_per_row_not_per_field() {
+        let params = InvertedIndexParams::new()
+            .with_field("title", 2.0, 0.5)
+            .with_field("body", 1.0, 0.75);
+        let builder = InvertedIndexBuilder::new(params);
+        let batch = string_batch(&[
+            ("title", vec!["lance index"]),
+            ("body", vec!["lance is fast"]),
+        ]);
+
+        let mut tokenizer = InvertedIndexTokenizer::new(Default::default());
+        let mut dictionary = HashMap::new();
+        let mut field_stats = HashMap::new();
+        let mut num_tokens = 0;
+        builder.accumulate_batch(
+            &batch,
+            &mut tokenizer,
+            &mut dictionary,
+            &mut field_stats,
+            &mut num_tokens,
+        );
+
+        // "lance" appears in both the title and body of this single row, so
+        // its document frequency must be 1, not 2
+        assert_eq!(dictionary["lance"], 1);
+
+        let scorer = AnyScorer::new_bm25f(
+            dictionary,
+            1,
+            num_tokens,
+            &builder.params().scorer,
+            field_stats,
+            builder.params().fields.clone(),
+        );
+        match scorer {
+            AnyScorer::Bm25(bm25) => assert_eq!(bm25.nq("lance"), 1),
+            other => panic!("expected a Bm25 scorer, found {:?}", other.model()),
+        }
+    }
+}
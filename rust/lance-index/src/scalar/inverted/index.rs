@@ -0,0 +1,183 @@
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-FileCopyrightText: Copyright The Lance Authors
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use lance_core::Result;
+
+use super::query::{fuzziness_for_token, levenshtein_distance, FtsSearchParams, FuzzyMatch};
+use super::scorer::{AnyScorer, FieldStats};
+use super::tokenizer::InvertedIndexTokenizer;
+
+/// The per-partition statistics and scorer state of an inverted index.
+///
+/// Indexes can be built incrementally, one fragment at a time; each
+/// partition is scored independently and merged with [`AnyScorer::merge`]
+/// at query time.
+#[derive(Debug, Clone)]
+pub struct InvertedPartitionMetadata {
+    id: u64,
+    num_tokens: usize,
+    num_docs: usize,
+    fragments: Vec<u32>,
+    scorer: AnyScorer,
+    // the document frequency of every token in this partition, used to
+    // expand fuzzy query tokens against the partition's own vocabulary
+    dictionary: HashMap<String, usize>,
+    // per-field token/doc counts, populated for a BM25F multi-column index;
+    // empty for a single-column index
+    field_stats: HashMap<String, FieldStats>,
+}
+
+impl InvertedPartitionMetadata {
+    pub fn new(
+        id: u64,
+        num_tokens: usize,
+        num_docs: usize,
+        fragments: Vec<u32>,
+        scorer: AnyScorer,
+        dictionary: HashMap<String, usize>,
+        field_stats: HashMap<String, FieldStats>,
+    ) -> Self {
+        Self {
+            id,
+            num_tokens,
+            num_docs,
+            fragments,
+            scorer,
+            dictionary,
+            field_stats,
+        }
+    }
+
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn num_tokens(&self) -> usize {
+        self.num_tokens
+    }
+
+    pub fn num_docs(&self) -> usize {
+        self.num_docs
+    }
+
+    pub fn fragments(&self) -> &[u32] {
+        &self.fragments
+    }
+
+    pub fn scorer(&self) -> &AnyScorer {
+        &self.scorer
+    }
+
+    pub fn dictionary(&self) -> &HashMap<String, usize> {
+        &self.dictionary
+    }
+
+    pub fn field_stats(&self) -> &HashMap<String, FieldStats> {
+        &self.field_stats
+    }
+}
+
+/// An inverted (full-text search) index over one column, or over several
+/// columns scored BM25F-style.
+pub struct InvertedIndex {
+    tokenizer: InvertedIndexTokenizer,
+    partitions: Vec<Arc<InvertedPartitionMetadata>>,
+}
+
+impl InvertedIndex {
+    pub fn new(
+        tokenizer: InvertedIndexTokenizer,
+        partitions: Vec<Arc<InvertedPartitionMetadata>>,
+    ) -> Self {
+        Self {
+            tokenizer,
+            partitions,
+        }
+    }
+
+    pub fn tokenizer(&self) -> &InvertedIndexTokenizer {
+        &self.tokenizer
+    }
+
+    pub fn partitions(&self) -> &[Arc<InvertedPartitionMetadata>] {
+        &self.partitions
+    }
+
+    fn partitions_for(&self, partition_ids: Option<&Vec<u64>>) -> Vec<&Arc<InvertedPartitionMetadata>> {
+        match partition_ids {
+            Some(ids) => self
+                .partitions
+                .iter()
+                .filter(|p| ids.contains(&p.id()))
+                .collect(),
+            None => self.partitions.iter().collect(),
+        }
+    }
+
+    /// Expands `tokens` into the vocabulary tokens within fuzzy-matching
+    /// distance, along with their document frequency and the edit distance
+    /// that matched them, honoring `params.prefix_length` and capping each
+    /// query token's expansion at `params.max_expansions`.
+    ///
+    /// When `params.fuzziness` is `None`, the allowed distance is derived
+    /// per-token from its length (see [`fuzziness_for_token`]) instead of a
+    /// single distance shared by every token.
+    pub async fn fuzzy_nq(
+        &self,
+        tokens: &HashSet<String>,
+        params: &FtsSearchParams,
+        partition_ids: Option<&Vec<u64>>,
+    ) -> Result<HashMap<String, FuzzyMatch>> {
+        let partitions = self.partitions_for(partition_ids);
+        let mut result: HashMap<String, FuzzyMatch> = HashMap::new();
+        for token in tokens {
+            let max_distance = fuzziness_for_token(params.fuzziness, token);
+            let prefix = token
+                .chars()
+                .take(params.prefix_length as usize)
+                .collect::<String>();
+
+            // a candidate's per-partition document frequencies are summed
+            // into its document frequency across the selected partitions
+            let mut matched: HashMap<String, (usize, u32)> = HashMap::new();
+            for partition in &partitions {
+                for (candidate, nq) in partition.dictionary().iter() {
+                    if !candidate.starts_with(&prefix) {
+                        continue;
+                    }
+                    let distance = if max_distance == 0 {
+                        if candidate != token {
+                            continue;
+                        }
+                        0
+                    } else {
+                        levenshtein_distance(candidate, token)
+                    };
+                    if distance <= max_distance {
+                        matched.entry(candidate.clone()).or_insert((0, distance)).0 += *nq;
+                    }
+                }
+            }
+            let mut candidates = matched
+                .into_iter()
+                .map(|(candidate, (nq, distance))| (candidate, nq, distance))
+                .collect::<Vec<_>>();
+            candidates.sort_by(|a, b| b.1.cmp(&a.1));
+            candidates.truncate(params.max_expansions);
+            for (candidate, nq, distance) in candidates {
+                // `nq` is the candidate's true corpus document frequency, so
+                // it must not be summed again just because more than one
+                // query token fuzzy-matched the same candidate; keep the
+                // smallest edit distance across those matches instead.
+                result
+                    .entry(candidate)
+                    .and_modify(|m| m.distance = m.distance.min(distance))
+                    .or_insert(FuzzyMatch { nq, distance });
+            }
+        }
+        Ok(result)
+    }
+}
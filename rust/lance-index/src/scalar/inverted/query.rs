@@ -0,0 +1,120 @@
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-FileCopyrightText: Copyright The Lance Authors
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use super::tokenizer::InvertedIndexTokenizer;
+
+/// Parameters controlling how a full-text query is executed.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FtsSearchParams {
+    /// The maximum number of fuzzy-matched tokens to expand a query token
+    /// into.
+    pub max_expansions: usize,
+    /// The allowed Levenshtein edit distance for fuzzy matching. `Some(n)`
+    /// applies `n` to every token; `None` derives the distance per token
+    /// from its length (see [`fuzziness_for_token`]).
+    pub fuzziness: Option<u32>,
+    /// The number of leading characters of a token that must match exactly
+    /// when fuzzy matching.
+    pub prefix_length: u32,
+}
+
+impl Default for FtsSearchParams {
+    fn default() -> Self {
+        Self {
+            max_expansions: 50,
+            fuzziness: Some(0),
+            prefix_length: 0,
+        }
+    }
+}
+
+impl FtsSearchParams {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Tokenizes `query`, optionally keeping only tokens present in `inclusive`.
+pub fn collect_tokens(
+    query: &str,
+    tokenizer: &mut InvertedIndexTokenizer,
+    inclusive: Option<&HashSet<String>>,
+) -> Vec<String> {
+    tokenizer
+        .tokenize(query)
+        .into_iter()
+        .filter(|token| inclusive.map_or(true, |inclusive| inclusive.contains(token)))
+        .collect()
+}
+
+/// The Levenshtein edit distance to use when fuzzy-matching `token`.
+///
+/// `fuzziness` fixes the distance for every token. `None` selects the
+/// length-adaptive ("AUTO") mode, which scales the allowed distance with
+/// `token`'s character count: 0..=3 chars requires an exact match, 4..=7
+/// chars allows a single edit, and 8+ chars allows two edits. A fixed
+/// distance applied uniformly would let a stray edit swamp a short token's
+/// few characters while barely constraining a long one, so AUTO mode scales
+/// the budget to the token instead.
+pub fn fuzziness_for_token(fuzziness: Option<u32>, token: &str) -> u32 {
+    fuzziness.unwrap_or_else(|| match token.chars().count() {
+        0..=3 => 0,
+        4..=7 => 1,
+        _ => 2,
+    })
+}
+
+/// A vocabulary token that fuzzy-matched a query token, along with the
+/// document frequency and edit distance that produced the match.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FuzzyMatch {
+    pub nq: usize,
+    pub distance: u32,
+}
+
+/// The Levenshtein edit distance between `a` and `b`.
+pub fn levenshtein_distance(a: &str, b: &str) -> u32 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<u32> = (0..=b.len() as u32).collect();
+    let mut cur = vec![0u32; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        cur[0] = i as u32 + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == cb { 0 } else { 1 };
+            cur[j + 1] = (prev[j + 1] + 1).min(cur[j] + 1).min(prev[j] + cost);
+        }
+        std::mem::swap(&mut prev, &mut cur);
+    }
+    prev[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fuzziness_for_token_honors_explicit_distance_regardless_of_length() {
+        assert_eq!(fuzziness_for_token(Some(3), "a"), 3);
+        assert_eq!(fuzziness_for_token(Some(0), "somewhatlongtoken"), 0);
+    }
+
+    #[test]
+    fn fuzziness_for_token_auto_mode_scales_with_length() {
+        assert_eq!(fuzziness_for_token(None, "cat"), 0); // 3 chars: exact
+        assert_eq!(fuzziness_for_token(None, "cats"), 1); // 4 chars: one edit
+        assert_eq!(fuzziness_for_token(None, "catalog"), 1); // 7 chars: one edit
+        assert_eq!(fuzziness_for_token(None, "catalogs"), 2); // 8 chars: two edits
+    }
+
+    #[test]
+    fn levenshtein_distance_basic_cases() {
+        assert_eq!(levenshtein_distance("same", "same"), 0);
+        assert_eq!(levenshtein_distance("cat", "cats"), 1);
+        assert_eq!(levenshtein_distance("kitten", "sitting"), 3);
+    }
+}
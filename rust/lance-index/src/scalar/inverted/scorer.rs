@@ -21,26 +21,153 @@ pub trait Scorer: Send + Sync {
     fn merge(scorers: &[&Self]) -> Self;
 }
 
-// BM25 parameters
-pub const K1: f32 = 1.2;
-pub const B: f32 = 0.75;
+// default BM25 / BM25L parameters, used when `ScorerParams` doesn't override them
+pub const DEFAULT_K1: f32 = 1.2;
+pub const DEFAULT_B: f32 = 0.75;
+pub const DEFAULT_BM25L_DELTA: f32 = 0.5;
 
-#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+fn default_k1() -> f32 {
+    DEFAULT_K1
+}
+
+fn default_b() -> f32 {
+    DEFAULT_B
+}
+
+fn default_delta() -> f32 {
+    DEFAULT_BM25L_DELTA
+}
+
+/// The scoring model an inverted index is trained with.
+///
+/// This is stored alongside the index so that a query always scores with
+/// whatever model (and parameters) the index was built with, regardless of
+/// what the current default happens to be.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ScorerModel {
+    #[default]
+    Bm25,
+    TfIdf,
+    Bm25L,
+}
+
+/// Parameters controlling the scoring model.
+///
+/// `k1` and `b` are only used by the BM25 family (`Bm25` and `Bm25L`),
+/// `delta` is only used by `Bm25L`; the other models ignore fields that
+/// don't apply to them.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct ScorerParams {
+    pub model: ScorerModel,
+    #[serde(default = "default_k1")]
+    pub k1: f32,
+    #[serde(default = "default_b")]
+    pub b: f32,
+    #[serde(default = "default_delta")]
+    pub delta: f32,
+}
+
+impl Default for ScorerParams {
+    fn default() -> Self {
+        Self {
+            model: ScorerModel::default(),
+            k1: DEFAULT_K1,
+            b: DEFAULT_B,
+            delta: DEFAULT_BM25L_DELTA,
+        }
+    }
+}
+
+/// Per-field corpus statistics for a BM25F-scored multi-column index.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+pub struct FieldStats {
+    pub num_tokens: usize,
+    pub num_docs: usize,
+}
+
+impl FieldStats {
+    pub fn avgdl(&self) -> f32 {
+        self.num_tokens as f32 / self.num_docs as f32
+    }
+}
+
+/// The boost and length-normalization parameter for one field of a
+/// BM25F-scored multi-column index.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct FieldWeight {
+    pub boost: f32,
+    pub b: f32,
+}
+
+impl Default for FieldWeight {
+    fn default() -> Self {
+        Self {
+            boost: 1.0,
+            b: DEFAULT_B,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct BM25Scorer {
     nqs: HashMap<String, usize>,
     num_docs: usize,
     num_tokens: usize,
     avgdl: f32,
+    #[serde(default = "default_k1")]
+    k1: f32,
+    #[serde(default = "default_b")]
+    b: f32,
+    // per-field stats/weights, populated when the index spans more than one
+    // column; empty for a single-column index
+    #[serde(default)]
+    field_stats: HashMap<String, FieldStats>,
+    #[serde(default)]
+    field_weights: HashMap<String, FieldWeight>,
 }
 
 impl BM25Scorer {
     pub fn new(nqs: HashMap<String, usize>, num_docs: usize, num_tokens: usize) -> Self {
+        Self::with_params(nqs, num_docs, num_tokens, DEFAULT_K1, DEFAULT_B)
+    }
+
+    pub fn with_params(
+        nqs: HashMap<String, usize>,
+        num_docs: usize,
+        num_tokens: usize,
+        k1: f32,
+        b: f32,
+    ) -> Self {
+        Self::with_fields(
+            nqs,
+            num_docs,
+            num_tokens,
+            k1,
+            b,
+            HashMap::new(),
+            HashMap::new(),
+        )
+    }
+
+    pub fn with_fields(
+        nqs: HashMap<String, usize>,
+        num_docs: usize,
+        num_tokens: usize,
+        k1: f32,
+        b: f32,
+        field_stats: HashMap<String, FieldStats>,
+        field_weights: HashMap<String, FieldWeight>,
+    ) -> Self {
         let avgdl = num_tokens as f32 / num_docs as f32;
         Self {
             nqs,
             num_docs,
             num_tokens,
             avgdl,
+            k1,
+            b,
+            field_stats,
+            field_weights,
         }
     }
 
@@ -52,10 +179,57 @@ impl BM25Scorer {
         self.avgdl
     }
 
-    // the number of documents that contain the token
+    pub fn k1(&self) -> f32 {
+        self.k1
+    }
+
+    pub fn b(&self) -> f32 {
+        self.b
+    }
+
+    pub fn field_stats(&self) -> &HashMap<String, FieldStats> {
+        &self.field_stats
+    }
+
+    // the number of documents that contain the token, in any field
     pub fn nq(&self, token: &str) -> usize {
         *self.nqs.get(token).unwrap_or(&1)
     }
+
+    /// Scores `token` against a multi-field document using BM25F: the
+    /// per-field term frequencies are combined into a single pseudo
+    /// frequency *before* saturation, each normalized by its own field's
+    /// average length and weighted by its boost, and the combined
+    /// frequency is then run through the usual BM25 saturation curve.
+    ///
+    /// `field_freqs` maps each field that contains `token` to
+    /// `(freq, field_doc_tokens)`, i.e. the term frequency and the
+    /// document's length within that field.
+    pub fn score_bm25f(&self, token: &str, field_freqs: &HashMap<String, (u32, u32)>) -> f32 {
+        let mut pseudo_freq = 0.0;
+        for (field, (freq, field_doc_tokens)) in field_freqs {
+            if *freq == 0 {
+                continue;
+            }
+            let weight = self.field_weights.get(field).copied().unwrap_or_default();
+            let avgdl = self
+                .field_stats
+                .get(field)
+                .map(FieldStats::avgdl)
+                .unwrap_or(self.avgdl);
+            let freq = *freq as f32;
+            let field_doc_tokens = *field_doc_tokens as f32;
+            pseudo_freq +=
+                weight.boost * freq / (1.0 - weight.b + weight.b * field_doc_tokens / avgdl);
+        }
+        self.query_weight(token) * (self.k1 + 1.0) * pseudo_freq / (self.k1 + pseudo_freq)
+    }
+}
+
+impl Default for BM25Scorer {
+    fn default() -> Self {
+        Self::with_params(HashMap::new(), 0, 0, DEFAULT_K1, DEFAULT_B)
+    }
 }
 
 impl Scorer for BM25Scorer {
@@ -63,14 +237,25 @@ impl Scorer for BM25Scorer {
         let mut nqs = HashMap::new();
         let mut num_docs = 0;
         let mut num_tokens = 0;
+        let mut field_stats: HashMap<String, FieldStats> = HashMap::new();
         for scorer in scorers {
             for (token, nq) in scorer.nqs.iter() {
                 *nqs.entry(token.clone()).or_insert(0) += nq;
             }
             num_docs += scorer.num_docs;
             num_tokens += scorer.num_tokens;
+            for (field, stats) in scorer.field_stats.iter() {
+                let entry = field_stats.entry(field.clone()).or_default();
+                entry.num_tokens += stats.num_tokens;
+                entry.num_docs += stats.num_docs;
+            }
         }
-        Self::new(nqs, num_docs, num_tokens)
+        // every partition of an index is trained with the same scorer parameters
+        let (k1, b, field_weights) = scorers
+            .first()
+            .map(|s| (s.k1, s.b, s.field_weights.clone()))
+            .unwrap_or((DEFAULT_K1, DEFAULT_B, HashMap::new()));
+        Self::with_fields(nqs, num_docs, num_tokens, k1, b, field_stats, field_weights)
     }
 
     fn query_weight(&self, token: &str) -> f32 {
@@ -84,8 +269,322 @@ impl Scorer for BM25Scorer {
     fn doc_weight(&self, freq: u32, doc_tokens: u32) -> f32 {
         let freq = freq as f32;
         let doc_tokens = doc_tokens as f32;
-        let doc_norm = K1 * (1.0 - B + B * doc_tokens / self.avgdl);
-        (K1 + 1.0) * freq / (freq + doc_norm)
+        let doc_norm = self.k1 * (1.0 - self.b + self.b * doc_tokens / self.avgdl);
+        (self.k1 + 1.0) * freq / (freq + doc_norm)
+    }
+}
+
+/// Classic TF-IDF: `query_weight = idf`, `doc_weight = 1 + ln(freq)`.
+///
+/// Unlike BM25 this model doesn't saturate term frequency or normalize by
+/// document length, which makes it a cheap baseline to compare BM25
+/// against.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct TfIdfScorer {
+    nqs: HashMap<String, usize>,
+    num_docs: usize,
+}
+
+impl TfIdfScorer {
+    pub fn new(nqs: HashMap<String, usize>, num_docs: usize) -> Self {
+        Self { nqs, num_docs }
+    }
+
+    pub fn num_docs(&self) -> usize {
+        self.num_docs
+    }
+
+    pub fn nq(&self, token: &str) -> usize {
+        *self.nqs.get(token).unwrap_or(&1)
+    }
+}
+
+impl Scorer for TfIdfScorer {
+    fn merge(scorers: &[&Self]) -> Self {
+        let mut nqs = HashMap::new();
+        let mut num_docs = 0;
+        for scorer in scorers {
+            for (token, nq) in scorer.nqs.iter() {
+                *nqs.entry(token.clone()).or_insert(0) += nq;
+            }
+            num_docs += scorer.num_docs;
+        }
+        Self::new(nqs, num_docs)
+    }
+
+    fn query_weight(&self, token: &str) -> f32 {
+        let nq = self.nq(token);
+        if nq == 0 {
+            return 0.0;
+        }
+        idf(nq, self.num_docs)
+    }
+
+    fn doc_weight(&self, freq: u32, _doc_tokens: u32) -> f32 {
+        if freq == 0 {
+            return 0.0;
+        }
+        1.0 + (freq as f32).ln()
+    }
+}
+
+/// BM25L: a BM25 variant that adds a `delta` bias to the length-normalized
+/// term frequency, which reduces the penalty BM25 otherwise imposes on long
+/// documents.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Bm25LScorer {
+    nqs: HashMap<String, usize>,
+    num_docs: usize,
+    num_tokens: usize,
+    avgdl: f32,
+    #[serde(default = "default_k1")]
+    k1: f32,
+    #[serde(default = "default_b")]
+    b: f32,
+    #[serde(default = "default_delta")]
+    delta: f32,
+}
+
+impl Bm25LScorer {
+    pub fn new(nqs: HashMap<String, usize>, num_docs: usize, num_tokens: usize) -> Self {
+        Self::with_params(
+            nqs,
+            num_docs,
+            num_tokens,
+            DEFAULT_K1,
+            DEFAULT_B,
+            DEFAULT_BM25L_DELTA,
+        )
+    }
+
+    pub fn with_params(
+        nqs: HashMap<String, usize>,
+        num_docs: usize,
+        num_tokens: usize,
+        k1: f32,
+        b: f32,
+        delta: f32,
+    ) -> Self {
+        let avgdl = num_tokens as f32 / num_docs as f32;
+        Self {
+            nqs,
+            num_docs,
+            num_tokens,
+            avgdl,
+            k1,
+            b,
+            delta,
+        }
+    }
+
+    pub fn nq(&self, token: &str) -> usize {
+        *self.nqs.get(token).unwrap_or(&1)
+    }
+}
+
+impl Scorer for Bm25LScorer {
+    fn merge(scorers: &[&Self]) -> Self {
+        let mut nqs = HashMap::new();
+        let mut num_docs = 0;
+        let mut num_tokens = 0;
+        for scorer in scorers {
+            for (token, nq) in scorer.nqs.iter() {
+                *nqs.entry(token.clone()).or_insert(0) += nq;
+            }
+            num_docs += scorer.num_docs;
+            num_tokens += scorer.num_tokens;
+        }
+        let (k1, b, delta) = scorers
+            .first()
+            .map(|s| (s.k1, s.b, s.delta))
+            .unwrap_or((DEFAULT_K1, DEFAULT_B, DEFAULT_BM25L_DELTA));
+        Self::with_params(nqs, num_docs, num_tokens, k1, b, delta)
+    }
+
+    fn query_weight(&self, token: &str) -> f32 {
+        let nq = self.nq(token);
+        if nq == 0 {
+            return 0.0;
+        }
+        idf(nq, self.num_docs)
+    }
+
+    fn doc_weight(&self, freq: u32, doc_tokens: u32) -> f32 {
+        let freq = freq as f32;
+        let doc_tokens = doc_tokens as f32;
+        let normalized = freq / (1.0 - self.b + self.b * doc_tokens / self.avgdl);
+        (self.k1 + 1.0) * (normalized + self.delta) / (self.k1 + normalized + self.delta)
+    }
+}
+
+/// A scoring model selected at index build time.
+///
+/// `Scorer::merge` can't be called through a trait object (it returns
+/// `Self`), so partitions of an index are merged through this enum instead,
+/// matching each partition against the model it was actually built with.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum AnyScorer {
+    Bm25(BM25Scorer),
+    TfIdf(TfIdfScorer),
+    Bm25L(Bm25LScorer),
+}
+
+impl AnyScorer {
+    pub fn new(
+        model: ScorerModel,
+        nqs: HashMap<String, usize>,
+        num_docs: usize,
+        num_tokens: usize,
+        params: &ScorerParams,
+    ) -> Self {
+        match model {
+            ScorerModel::Bm25 => Self::Bm25(BM25Scorer::with_params(
+                nqs, num_docs, num_tokens, params.k1, params.b,
+            )),
+            ScorerModel::TfIdf => Self::TfIdf(TfIdfScorer::new(nqs, num_docs)),
+            ScorerModel::Bm25L => Self::Bm25L(Bm25LScorer::with_params(
+                nqs,
+                num_docs,
+                num_tokens,
+                params.k1,
+                params.b,
+                params.delta,
+            )),
+        }
+    }
+
+    /// Builds a BM25F scorer: a BM25 scorer carrying per-field corpus
+    /// statistics and boosts, used by multi-column inverted indexes.
+    pub fn new_bm25f(
+        nqs: HashMap<String, usize>,
+        num_docs: usize,
+        num_tokens: usize,
+        params: &ScorerParams,
+        field_stats: HashMap<String, FieldStats>,
+        field_weights: HashMap<String, FieldWeight>,
+    ) -> Self {
+        Self::Bm25(BM25Scorer::with_fields(
+            nqs,
+            num_docs,
+            num_tokens,
+            params.k1,
+            params.b,
+            field_stats,
+            field_weights,
+        ))
+    }
+
+    pub fn model(&self) -> ScorerModel {
+        match self {
+            Self::Bm25(_) => ScorerModel::Bm25,
+            Self::TfIdf(_) => ScorerModel::TfIdf,
+            Self::Bm25L(_) => ScorerModel::Bm25L,
+        }
+    }
+
+    pub fn num_docs(&self) -> usize {
+        match self {
+            Self::Bm25(s) => s.num_docs(),
+            Self::TfIdf(s) => s.num_docs(),
+            Self::Bm25L(s) => s.num_docs,
+        }
+    }
+
+    /// Merges `scorers`, failing instead of panicking if they weren't all
+    /// trained with the same model. Prefer this over [`Scorer::merge`]
+    /// whenever the scorers being merged come from outside this crate
+    /// (e.g. across an FFI boundary), since a caller there has no way to
+    /// guarantee the scorers are homogeneous ahead of time.
+    pub fn try_merge(scorers: &[&Self]) -> std::result::Result<Self, ScorerMergeError> {
+        let Some(model) = scorers.first().map(|s| s.model()) else {
+            return Ok(Self::default());
+        };
+        if let Some(mismatched) = scorers.iter().find(|s| s.model() != model) {
+            return Err(ScorerMergeError {
+                expected: model,
+                found: mismatched.model(),
+            });
+        }
+
+        Ok(match model {
+            ScorerModel::Bm25 => Self::Bm25(BM25Scorer::merge(
+                &scorers
+                    .iter()
+                    .map(|s| match s {
+                        Self::Bm25(s) => s,
+                        _ => unreachable!("model mismatch already checked above"),
+                    })
+                    .collect::<Vec<_>>(),
+            )),
+            ScorerModel::TfIdf => Self::TfIdf(TfIdfScorer::merge(
+                &scorers
+                    .iter()
+                    .map(|s| match s {
+                        Self::TfIdf(s) => s,
+                        _ => unreachable!("model mismatch already checked above"),
+                    })
+                    .collect::<Vec<_>>(),
+            )),
+            ScorerModel::Bm25L => Self::Bm25L(Bm25LScorer::merge(
+                &scorers
+                    .iter()
+                    .map(|s| match s {
+                        Self::Bm25L(s) => s,
+                        _ => unreachable!("model mismatch already checked above"),
+                    })
+                    .collect::<Vec<_>>(),
+            )),
+        })
+    }
+}
+
+/// The scorers passed to [`AnyScorer::try_merge`] weren't all trained with
+/// the same [`ScorerModel`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScorerMergeError {
+    pub expected: ScorerModel,
+    pub found: ScorerModel,
+}
+
+impl std::fmt::Display for ScorerMergeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "cannot merge scorers trained with different models: expected {:?}, found {:?}",
+            self.expected, self.found
+        )
+    }
+}
+
+impl std::error::Error for ScorerMergeError {}
+
+impl Scorer for AnyScorer {
+    fn merge(scorers: &[&Self]) -> Self {
+        Self::try_merge(scorers).unwrap_or_else(|err| {
+            panic!("{err}: every partition of an index must be trained with the same scorer")
+        })
+    }
+
+    fn query_weight(&self, token: &str) -> f32 {
+        match self {
+            Self::Bm25(s) => s.query_weight(token),
+            Self::TfIdf(s) => s.query_weight(token),
+            Self::Bm25L(s) => s.query_weight(token),
+        }
+    }
+
+    fn doc_weight(&self, freq: u32, doc_tokens: u32) -> f32 {
+        match self {
+            Self::Bm25(s) => s.doc_weight(freq, doc_tokens),
+            Self::TfIdf(s) => s.doc_weight(freq, doc_tokens),
+            Self::Bm25L(s) => s.doc_weight(freq, doc_tokens),
+        }
+    }
+}
+
+impl Default for AnyScorer {
+    fn default() -> Self {
+        Self::Bm25(BM25Scorer::default())
     }
 }
 
@@ -94,3 +593,119 @@ pub fn idf(nq: usize, num_docs: usize) -> f32 {
     let num_docs = num_docs as f32;
     ((num_docs - nq as f32 + 0.5) / (nq as f32 + 0.5) + 1.0).ln()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn score_bm25f_matches_hand_computed_formula() {
+        // two fields, "title" (short, boosted) and "body" (long), each with
+        // its own avgdl and b; "token" appears in both fields of this doc
+        let mut field_stats = HashMap::new();
+        field_stats.insert(
+            "title".to_string(),
+            FieldStats {
+                num_tokens: 20,
+                num_docs: 10,
+            },
+        );
+        field_stats.insert(
+            "body".to_string(),
+            FieldStats {
+                num_tokens: 1000,
+                num_docs: 10,
+            },
+        );
+        let mut field_weights = HashMap::new();
+        field_weights.insert(
+            "title".to_string(),
+            FieldWeight { boost: 2.0, b: 0.5 },
+        );
+        field_weights.insert(
+            "body".to_string(),
+            FieldWeight { boost: 1.0, b: 0.75 },
+        );
+        let mut nqs = HashMap::new();
+        nqs.insert("token".to_string(), 4);
+        let scorer = BM25Scorer::with_fields(nqs, 10, 2020, 1.2, 0.75, field_stats, field_weights);
+
+        let mut field_freqs = HashMap::new();
+        field_freqs.insert("title".to_string(), (1, 2));
+        field_freqs.insert("body".to_string(), (3, 100));
+
+        // title: 2.0 * 1 / (1 - 0.5 + 0.5 * 2 / 2.0) = 2.0
+        // body: 1.0 * 3 / (1 - 0.75 + 0.75 * 100 / 100.0) = 3.0
+        let pseudo_freq = 2.0 + 3.0;
+        let k1 = 1.2;
+        let expected = scorer.query_weight("token") * (k1 + 1.0) * pseudo_freq / (k1 + pseudo_freq);
+
+        assert!((scorer.score_bm25f("token", &field_freqs) - expected).abs() < 1e-5);
+    }
+
+    #[test]
+    fn score_bm25f_ignores_fields_with_zero_frequency() {
+        let scorer = BM25Scorer::with_params(HashMap::new(), 10, 1000, 1.2, 0.75);
+        let mut field_freqs = HashMap::new();
+        field_freqs.insert("title".to_string(), (0, 5));
+        assert_eq!(scorer.score_bm25f("token", &field_freqs), 0.0);
+    }
+
+    #[test]
+    fn bm25_scorer_merge_sums_corpus_and_field_stats() {
+        let mut nqs_a = HashMap::new();
+        nqs_a.insert("a".to_string(), 2);
+        let mut field_stats_a = HashMap::new();
+        field_stats_a.insert(
+            "title".to_string(),
+            FieldStats {
+                num_tokens: 10,
+                num_docs: 5,
+            },
+        );
+        let a = BM25Scorer::with_fields(nqs_a, 5, 50, 1.2, 0.75, field_stats_a, HashMap::new());
+
+        let mut nqs_b = HashMap::new();
+        nqs_b.insert("a".to_string(), 3);
+        let mut field_stats_b = HashMap::new();
+        field_stats_b.insert(
+            "title".to_string(),
+            FieldStats {
+                num_tokens: 20,
+                num_docs: 5,
+            },
+        );
+        let b = BM25Scorer::with_fields(nqs_b, 5, 50, 1.2, 0.75, field_stats_b, HashMap::new());
+
+        let merged = BM25Scorer::merge(&[&a, &b]);
+        assert_eq!(merged.num_docs(), 10);
+        assert_eq!(merged.nq("a"), 5);
+        assert_eq!(merged.field_stats()["title"].num_tokens, 30);
+        assert_eq!(merged.field_stats()["title"].num_docs, 10);
+    }
+
+    #[test]
+    fn any_scorer_try_merge_rejects_mismatched_models() {
+        let bm25 = AnyScorer::Bm25(BM25Scorer::default());
+        let tfidf = AnyScorer::TfIdf(TfIdfScorer::default());
+
+        let err = AnyScorer::try_merge(&[&bm25, &tfidf]).unwrap_err();
+        assert_eq!(err.expected, ScorerModel::Bm25);
+        assert_eq!(err.found, ScorerModel::TfIdf);
+    }
+
+    #[test]
+    fn any_scorer_try_merge_accepts_same_model() {
+        let a = AnyScorer::Bm25(BM25Scorer::default());
+        let b = AnyScorer::Bm25(BM25Scorer::default());
+        assert!(AnyScorer::try_merge(&[&a, &b]).is_ok());
+    }
+
+    #[test]
+    #[should_panic(expected = "every partition of an index must be trained with the same scorer")]
+    fn any_scorer_merge_panics_on_mismatched_models() {
+        let bm25 = AnyScorer::Bm25(BM25Scorer::default());
+        let tfidf = AnyScorer::TfIdf(TfIdfScorer::default());
+        let _ = <AnyScorer as Scorer>::merge(&[&bm25, &tfidf]);
+    }
+}
@@ -0,0 +1,46 @@
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-FileCopyrightText: Copyright The Lance Authors
+
+use serde::{Deserialize, Serialize};
+
+/// Configuration for the tokenizer an inverted index is built with.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct InvertedIndexTokenizerConfig {
+    pub base_tokenizer: String,
+    pub lower_case: bool,
+}
+
+impl Default for InvertedIndexTokenizerConfig {
+    fn default() -> Self {
+        Self {
+            base_tokenizer: "simple".to_string(),
+            lower_case: true,
+        }
+    }
+}
+
+/// Splits document/query text into the tokens an inverted index is built
+/// and queried with.
+#[derive(Debug, Clone, Default)]
+pub struct InvertedIndexTokenizer {
+    config: InvertedIndexTokenizerConfig,
+}
+
+impl InvertedIndexTokenizer {
+    pub fn new(config: InvertedIndexTokenizerConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn tokenize(&mut self, text: &str) -> Vec<String> {
+        text.split(|c: char| !c.is_alphanumeric())
+            .filter(|token| !token.is_empty())
+            .map(|token| {
+                if self.config.lower_case {
+                    token.to_lowercase()
+                } else {
+                    token.to_string()
+                }
+            })
+            .collect()
+    }
+}